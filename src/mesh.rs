@@ -0,0 +1,239 @@
+//! Triangle mesh export built from a `BlueNoiseSphere` point set.
+//!
+//! For points already lying on a sphere the convex hull coincides with the
+//! spherical Delaunay triangulation, so a 3D incremental convex hull
+//! (every input point becomes a hull vertex) is enough to turn the point
+//! cloud into a usable mesh.
+
+use glam::Vec3;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+/// A triangle mesh: a flat vertex buffer plus a flat, 0-indexed triangle index buffer.
+pub struct Mesh {
+    vertices: Vec<(f32, f32, f32)>,
+    indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub(crate) fn from_parts(vertices: Vec<(f32, f32, f32)>, indices: Vec<u32>) -> Mesh {
+        Mesh { vertices, indices }
+    }
+
+    /// The mesh's vertices, as `(x, y, z)` tuples.
+    pub fn vertices(&self) -> &[(f32, f32, f32)] {
+        &self.vertices
+    }
+
+    /// The mesh's triangles, as a flat buffer of vertex indices (3 per triangle).
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Writes the mesh to `path` as a Wavefront OBJ file.
+    pub fn export_obj<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut file = std::fs::File::create(path)?;
+
+        for vertex in &self.vertices {
+            writeln!(file, "v {} {} {}", vertex.0, vertex.1, vertex.2)?;
+        }
+
+        for triangle in self.indices.chunks(3) {
+            writeln!(
+                file,
+                "f {} {} {}",
+                triangle[0] + 1,
+                triangle[1] + 1,
+                triangle[2] + 1
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds the convex hull of `particles` via incremental insertion, which for
+/// points on a sphere coincides with their spherical Delaunay triangulation.
+///
+/// Panics if `particles` has fewer than 4 points, since a convex hull isn't defined below a tetrahedron.
+pub(crate) fn convex_hull(particles: &[Vec3]) -> Mesh {
+    assert!(
+        particles.len() >= 4,
+        "convex_hull needs at least 4 points, got {}",
+        particles.len()
+    );
+
+    let mut faces = initial_tetrahedron_faces(particles);
+    let (p0, p1, p2, p3) = initial_tetrahedron(particles);
+    let centroid = (particles[p0] + particles[p1] + particles[p2] + particles[p3]) / 4.0;
+
+    for point_index in 0..particles.len() {
+        if point_index == p0 || point_index == p1 || point_index == p2 || point_index == p3 {
+            continue;
+        }
+
+        let point = particles[point_index];
+
+        let visible: Vec<usize> = faces
+            .iter()
+            .enumerate()
+            .filter(|(_, &face)| {
+                face_normal(particles, face).dot(point - particles[face[0]]) > 0.000_001
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        if visible.is_empty() {
+            // Point lies inside (or on) the current hull; nothing to do.
+            continue;
+        }
+
+        let visible_set: HashSet<usize> = visible.iter().copied().collect();
+
+        let mut edges = HashSet::new();
+        for &face_index in &visible {
+            for &edge in &face_edges(faces[face_index]) {
+                edges.insert(edge);
+            }
+        }
+
+        let horizon: Vec<(usize, usize)> = visible
+            .iter()
+            .flat_map(|&face_index| face_edges(faces[face_index]).to_vec())
+            .filter(|&(a, b)| !edges.contains(&(b, a)))
+            .collect();
+
+        faces = faces
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !visible_set.contains(index))
+            .map(|(_, face)| face)
+            .collect();
+
+        faces.extend(horizon.into_iter().map(|(a, b)| [a, b, point_index]));
+    }
+
+    // Final sanity pass: make sure every face winds outward relative to the hull centroid.
+    for face in &mut faces {
+        if face_normal(particles, *face).dot(particles[face[0]] - centroid) < 0.0 {
+            face.swap(1, 2);
+        }
+    }
+
+    Mesh {
+        vertices: particles
+            .iter()
+            .map(|particle| (particle.x, particle.y, particle.z))
+            .collect(),
+        indices: faces
+            .iter()
+            .flat_map(|face| face.iter().map(|&index| index as u32))
+            .collect(),
+    }
+}
+
+fn face_normal(particles: &[Vec3], face: [usize; 3]) -> Vec3 {
+    let (a, b, c) = (particles[face[0]], particles[face[1]], particles[face[2]]);
+    (b - a).cross(c - a)
+}
+
+fn face_edges(face: [usize; 3]) -> [(usize, usize); 3] {
+    [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])]
+}
+
+fn initial_tetrahedron(particles: &[Vec3]) -> (usize, usize, usize, usize) {
+    let p0 = (0..particles.len())
+        .min_by(|&a, &b| particles[a].x.partial_cmp(&particles[b].x).unwrap())
+        .unwrap();
+
+    let p1 = (0..particles.len())
+        .max_by(|&a, &b| {
+            particles[a]
+                .distance_squared(particles[p0])
+                .partial_cmp(&particles[b].distance_squared(particles[p0]))
+                .unwrap()
+        })
+        .unwrap();
+
+    let axis = (particles[p1] - particles[p0]).normalize();
+    let p2 = (0..particles.len())
+        .max_by(|&a, &b| {
+            let perp = |i: usize| {
+                let offset = particles[i] - particles[p0];
+                (offset - axis * offset.dot(axis)).length_squared()
+            };
+            perp(a).partial_cmp(&perp(b)).unwrap()
+        })
+        .unwrap();
+
+    let plane_normal = (particles[p1] - particles[p0]).cross(particles[p2] - particles[p0]);
+    let p3 = (0..particles.len())
+        .max_by(|&a, &b| {
+            let dist = |i: usize| (particles[i] - particles[p0]).dot(plane_normal).abs();
+            dist(a).partial_cmp(&dist(b)).unwrap()
+        })
+        .unwrap();
+
+    (p0, p1, p2, p3)
+}
+
+fn initial_tetrahedron_faces(particles: &[Vec3]) -> Vec<[usize; 3]> {
+    let (p0, p1, p2, p3) = initial_tetrahedron(particles);
+    let centroid = (particles[p0] + particles[p1] + particles[p2] + particles[p3]) / 4.0;
+
+    [[p0, p1, p2], [p0, p2, p3], [p0, p3, p1], [p1, p3, p2]]
+        .into_iter()
+        .map(|mut face| {
+            if face_normal(particles, face).dot(particles[face[0]] - centroid) < 0.0 {
+                face.swap(1, 2);
+            }
+            face
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, UnitSphere};
+    use std::collections::HashSet;
+
+    fn sample_particles(n: usize, seed: u64) -> Vec<Vec3> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| {
+                let sample: [f32; 3] = UnitSphere.sample(&mut rng);
+                Vec3::new(sample[0], sample[1], sample[2])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn convex_hull_satisfies_euler_characteristic() {
+        let particles = sample_particles(200, 7);
+        let mesh = convex_hull(&particles);
+
+        let num_of_vertices = mesh.vertices().len();
+        let num_of_faces = mesh.indices().len() / 3;
+
+        let mut edges = HashSet::new();
+        for face in mesh.indices().chunks(3) {
+            for &(a, b) in &[(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                edges.insert((a.min(b), a.max(b)));
+            }
+        }
+
+        let euler_characteristic =
+            num_of_vertices as i64 - edges.len() as i64 + num_of_faces as i64;
+        assert_eq!(euler_characteristic, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn convex_hull_requires_at_least_four_points() {
+        let particles = sample_particles(3, 1);
+        convex_hull(&particles);
+    }
+}
@@ -0,0 +1,270 @@
+//! Barnes-Hut style octree over particle positions, used to turn the
+//! otherwise O(N^2) repulsion step into roughly O(N log N).
+//!
+//! Each node covers a cubic region of space and stores the count of
+//! particles it contains together with the sum of their Cartesian
+//! positions; dividing the sum by the count and normalizing back onto the
+//! unit sphere gives the direction of the node's pseudo-particle.
+
+use glam::Vec3;
+
+pub(crate) struct Octree {
+    center: Vec3,
+    half_extent: f32,
+    count: u32,
+    position_sum: Vec3,
+    /// Indices of the particles contained directly in this node; only populated for leaves, used to exclude a particle from its own leaf by identity rather than by comparing floating-point positions.
+    leaf_indices: Vec<usize>,
+    children: Option<Box<[Octree; 8]>>,
+}
+
+impl Octree {
+    const MAX_PARTICLES_PER_LEAF: usize = 1;
+    const MAX_DEPTH: u32 = 20;
+
+    /// Builds an octree over `particles`, which are assumed to lie on (or
+    /// near) the unit sphere and therefore fit within the cube of half
+    /// extent 1.0 centered at the origin.
+    pub(crate) fn build(particles: &[Vec3]) -> Self {
+        let indices: Vec<usize> = (0..particles.len()).collect();
+        Self::build_node(particles, &indices, Vec3::zero(), 1.0, 0)
+    }
+
+    fn build_node(
+        particles: &[Vec3],
+        indices: &[usize],
+        center: Vec3,
+        half_extent: f32,
+        depth: u32,
+    ) -> Self {
+        let count = indices.len() as u32;
+        let position_sum = indices
+            .iter()
+            .map(|&i| particles[i])
+            .fold(Vec3::zero(), |acc, position| acc + position);
+
+        if indices.len() <= Self::MAX_PARTICLES_PER_LEAF || depth >= Self::MAX_DEPTH {
+            return Octree {
+                center,
+                half_extent,
+                count,
+                position_sum,
+                leaf_indices: indices.to_vec(),
+                children: None,
+            };
+        }
+
+        let mut buckets: [Vec<usize>; 8] = Default::default();
+        for &i in indices {
+            buckets[octant_of(particles[i], center)].push(i);
+        }
+
+        let quarter = half_extent / 2.0;
+        let children = Box::new(std::array::from_fn(|octant| {
+            let child_center = center + octant_offset(octant) * quarter;
+            Self::build_node(
+                particles,
+                &buckets[octant],
+                child_center,
+                quarter,
+                depth + 1,
+            )
+        }));
+
+        Octree {
+            center,
+            half_extent,
+            count,
+            position_sum,
+            leaf_indices: Vec::new(),
+            children: Some(children),
+        }
+    }
+
+    /// Accumulates the Barnes-Hut approximate angular acceleration exerted
+    /// on the particle at `current_index` by every other particle contained
+    /// in this subtree, using the same `cross(...).normalize() / (angle^2 +
+    /// 1e-8)` formula as the exact `advance` step. A leaf holds more than
+    /// one particle only once `MAX_DEPTH` is reached (near-coincident
+    /// points); such leaves are summed exactly, excluding `current_index`
+    /// by identity, rather than treated as a single pseudo-particle, so a
+    /// particle never attracts or repels itself through its own leaf.
+    pub(crate) fn accumulate_force(
+        &self,
+        current_index: usize,
+        current_particle: Vec3,
+        particles: &[Vec3],
+        theta: f32,
+        acc: &mut Vec3,
+    ) {
+        if self.count == 0 {
+            return;
+        }
+
+        match &self.children {
+            None => {
+                for &index in &self.leaf_indices {
+                    if index == current_index {
+                        continue;
+                    }
+                    let other = particles[index];
+                    let angle = current_particle.angle_between(other);
+                    *acc -=
+                        current_particle.cross(other).normalize() / (angle.powi(2) + 0.00000001);
+                }
+            }
+            Some(children) => {
+                let centroid = (self.position_sum / self.count as f32).normalize();
+                let distance = current_particle.angle_between(centroid);
+                if self.half_extent / distance < theta {
+                    *acc -= current_particle.cross(centroid).normalize()
+                        / (distance.powi(2) + 0.00000001)
+                        * self.count as f32;
+                } else {
+                    for child in children.iter() {
+                        child.accumulate_force(
+                            current_index,
+                            current_particle,
+                            particles,
+                            theta,
+                            acc,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Narrows `best` down to the chord (Cartesian) distance from
+    /// `current_particle` to its nearest other particle in this subtree,
+    /// skipping a particle exactly equal to `current_particle`. Subtrees
+    /// whose cube cannot possibly contain anything closer than `best` are
+    /// pruned, which keeps the search close to O(log N) on average instead
+    /// of the O(N) exact scan.
+    pub(crate) fn nearest_chord_distance(&self, current_particle: Vec3, best: &mut f32) {
+        if self.count == 0 {
+            return;
+        }
+
+        // The node's cube is contained within a sphere of this radius around
+        // its center, so no point inside it can be closer than this bound.
+        let lower_bound = (current_particle.distance(self.center)
+            - self.half_extent * HALF_DIAGONAL_FACTOR)
+            .max(0.0);
+        if lower_bound >= *best {
+            return;
+        }
+
+        match &self.children {
+            None => {
+                let other = self.position_sum / self.count as f32;
+                if other == current_particle {
+                    return;
+                }
+                let distance = current_particle.distance(other);
+                if distance < *best {
+                    *best = distance;
+                }
+            }
+            Some(children) => {
+                for child in children.iter() {
+                    child.nearest_chord_distance(current_particle, best);
+                }
+            }
+        }
+    }
+}
+
+/// sqrt(3): the ratio between a cube's half-diagonal and its half-extent.
+const HALF_DIAGONAL_FACTOR: f32 = 1.732_050_8;
+
+fn octant_of(point: Vec3, center: Vec3) -> usize {
+    let mut octant = 0;
+    if point.x >= center.x {
+        octant |= 1;
+    }
+    if point.y >= center.y {
+        octant |= 2;
+    }
+    if point.z >= center.z {
+        octant |= 4;
+    }
+    octant
+}
+
+fn octant_offset(octant: usize) -> Vec3 {
+    Vec3::new(
+        if octant & 1 != 0 { 1.0 } else { -1.0 },
+        if octant & 2 != 0 { 1.0 } else { -1.0 },
+        if octant & 4 != 0 { 1.0 } else { -1.0 },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, UnitSphere};
+
+    fn sample_particles(n: usize, seed: u64) -> Vec<Vec3> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| {
+                let sample: [f32; 3] = UnitSphere.sample(&mut rng);
+                Vec3::new(sample[0], sample[1], sample[2])
+            })
+            .collect()
+    }
+
+    fn brute_force_acceleration(particles: &[Vec3], index: usize) -> Vec3 {
+        let current = particles[index];
+        particles
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != index)
+            .fold(Vec3::zero(), |acc, (_, &other)| {
+                acc - current.cross(other).normalize()
+                    / (current.angle_between(other).powi(2) + 0.00000001)
+            })
+    }
+
+    #[test]
+    fn accumulate_force_matches_brute_force_at_theta_zero() {
+        let particles = sample_particles(40, 1);
+        let tree = Octree::build(&particles);
+
+        for index in 0..particles.len() {
+            let mut approx = Vec3::zero();
+            tree.accumulate_force(index, particles[index], &particles, 0.0, &mut approx);
+            let brute = brute_force_acceleration(&particles, index);
+            assert!(
+                (approx - brute).length() < 0.001,
+                "particle {index}: octree force {approx:?} vs brute-force {brute:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn leaf_with_multiple_particles_excludes_only_the_query_index() {
+        // Reproduces a leaf that MAX_DEPTH has forced to hold more than one
+        // particle (near-coincident points). Regression test for a leaf
+        // excluding by averaged position instead of by particle index.
+        let particles = vec![Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+        let leaf = Octree {
+            center: Vec3::zero(),
+            half_extent: 1.0,
+            count: 2,
+            position_sum: particles[0] + particles[1],
+            leaf_indices: vec![0, 1],
+            children: None,
+        };
+
+        let mut acc = Vec3::zero();
+        leaf.accumulate_force(0, particles[0], &particles, 0.5, &mut acc);
+
+        let expected = -(particles[0].cross(particles[1]).normalize()
+            / (particles[0].angle_between(particles[1]).powi(2) + 0.00000001));
+
+        assert!((acc - expected).length() < 0.0001);
+    }
+}
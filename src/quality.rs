@@ -0,0 +1,95 @@
+//! Blue-noise quality metrics, giving an objective measure of pattern
+//! quality in place of guessing at iteration counts and decay ratios.
+
+use crate::octree::Octree;
+use glam::Vec3;
+
+/// Statistics over each point's nearest-neighbor geodesic distance, returned by `BlueNoiseSphere::quality`.
+#[derive(Clone, Copy)]
+pub struct QualityStats {
+    /// The mean nearest-neighbor geodesic distance, in radians.
+    pub mean_nearest_neighbor_distance: f32,
+    /// The smallest nearest-neighbor geodesic distance found, in radians.
+    pub min_nearest_neighbor_distance: f32,
+    /// The coefficient of variation (standard deviation / mean) of nearest-neighbor distances. Lower values indicate a more uniform blue-noise pattern.
+    pub coefficient_of_variation: f32,
+}
+
+/// Computes `QualityStats` over `particles`, backing the nearest-neighbor search with an octree so this stays close to O(N log N) rather than O(N^2).
+pub(crate) fn compute(particles: &[Vec3]) -> QualityStats {
+    let tree = Octree::build(particles);
+
+    let nearest_neighbor_distances: Vec<f32> = particles
+        .iter()
+        .map(|&particle| {
+            let mut nearest_chord_distance = f32::MAX;
+            tree.nearest_chord_distance(particle, &mut nearest_chord_distance);
+            chord_to_angle(nearest_chord_distance)
+        })
+        .collect();
+
+    let num_of_points = nearest_neighbor_distances.len() as f32;
+    let mean = nearest_neighbor_distances.iter().sum::<f32>() / num_of_points;
+    let min = nearest_neighbor_distances
+        .iter()
+        .cloned()
+        .fold(f32::MAX, f32::min);
+    let variance = nearest_neighbor_distances
+        .iter()
+        .map(|distance| (distance - mean).powi(2))
+        .sum::<f32>()
+        / num_of_points;
+
+    QualityStats {
+        mean_nearest_neighbor_distance: mean,
+        min_nearest_neighbor_distance: min,
+        coefficient_of_variation: variance.sqrt() / mean,
+    }
+}
+
+/// Converts a chord (Cartesian) distance between two unit vectors into the angle between them.
+fn chord_to_angle(chord: f32) -> f32 {
+    (1.0 - chord.powi(2) / 2.0).clamp(-1.0, 1.0).acos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, UnitSphere};
+
+    fn sample_particles(n: usize, seed: u64) -> Vec<Vec3> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| {
+                let sample: [f32; 3] = UnitSphere.sample(&mut rng);
+                Vec3::new(sample[0], sample[1], sample[2])
+            })
+            .collect()
+    }
+
+    fn brute_force_mean_nearest_neighbor(particles: &[Vec3]) -> f32 {
+        let distances: Vec<f32> = particles
+            .iter()
+            .enumerate()
+            .map(|(i, &particle)| {
+                particles
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &other)| particle.angle_between(other))
+                    .fold(f32::MAX, f32::min)
+            })
+            .collect();
+        distances.iter().sum::<f32>() / distances.len() as f32
+    }
+
+    #[test]
+    fn compute_matches_brute_force_nearest_neighbor_search() {
+        let particles = sample_particles(150, 3);
+        let stats = compute(&particles);
+        let brute_mean = brute_force_mean_nearest_neighbor(&particles);
+
+        assert!((stats.mean_nearest_neighbor_distance - brute_mean).abs() < 0.0001);
+    }
+}
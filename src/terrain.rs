@@ -0,0 +1,169 @@
+//! Fractal-noise radial displacement, turning a `Mesh` produced by
+//! `BlueNoiseSphere::to_mesh` into procedural planet terrain.
+
+use crate::Mesh;
+use glam::Vec3;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Parameters for a fractal-noise radial displacement pass; see `Mesh::displace_fbm` and `Mesh::displace_turbulence`.
+pub struct FbmParams {
+    /// Number of noise octaves to accumulate.
+    pub octaves: u32,
+    /// Starting sample frequency (the first octave samples noise at `point * frequency`).
+    pub frequency: f32,
+    /// Frequency multiplier applied after each octave; `~2.0` is typical.
+    pub lacunarity: f32,
+    /// Amplitude multiplier applied after each octave; `~0.5` is typical.
+    pub gain: f32,
+    /// How far the final normalized height displaces a vertex's radius from `1.0`, in either direction.
+    pub relief: f32,
+}
+
+impl Mesh {
+    /// Displaces each vertex radially using fractional Brownian motion: starting from `amplitude = 1` and `freq = params.frequency`, each octave samples a seeded 3D gradient noise at `point * freq` and accumulates `noise * amplitude` into the vertex's height, then `freq *= params.lacunarity` and `amplitude *= params.gain`. The vertex is finally moved to `radius = 1 + normalized_height * params.relief`. The triangulation is reused unchanged, so this goes straight from `to_mesh()` to a heightfielded planet.
+    pub fn displace_fbm<R: Rng + ?Sized>(&self, params: &FbmParams, rng: &mut R) -> Mesh {
+        self.displace_with(params, rng, |noise| 2.0 * noise - 1.0)
+    }
+
+    /// Same as `displace_fbm`, but accumulates `abs(2*noise - 1) * amplitude` per octave instead of `noise * amplitude`, producing ridged/turbulent terrain.
+    pub fn displace_turbulence<R: Rng + ?Sized>(&self, params: &FbmParams, rng: &mut R) -> Mesh {
+        self.displace_with(params, rng, |noise| (2.0 * noise - 1.0).abs())
+    }
+
+    fn displace_with<R: Rng + ?Sized>(
+        &self,
+        params: &FbmParams,
+        rng: &mut R,
+        octave_fn: impl Fn(f32) -> f32,
+    ) -> Mesh {
+        let noise = GradientNoise::new(rng);
+
+        let heights: Vec<f32> = self
+            .vertices()
+            .iter()
+            .map(|&(x, y, z)| {
+                let point = Vec3::new(x, y, z);
+                let mut amplitude = 1.0;
+                let mut freq = params.frequency;
+                let mut height = 0.0;
+
+                for _ in 0..params.octaves {
+                    let normalized_noise = (noise.sample(point * freq) + 1.0) / 2.0;
+                    height += octave_fn(normalized_noise) * amplitude;
+                    freq *= params.lacunarity;
+                    amplitude *= params.gain;
+                }
+
+                height
+            })
+            .collect();
+
+        let max_height = heights
+            .iter()
+            .fold(0.000_001_f32, |max, &height| max.max(height.abs()));
+
+        let vertices = self
+            .vertices()
+            .iter()
+            .zip(heights.iter())
+            .map(|(&(x, y, z), &height)| {
+                let radius = 1.0 + (height / max_height) * params.relief;
+                let displaced = Vec3::new(x, y, z).normalize() * radius;
+                (displaced.x, displaced.y, displaced.z)
+            })
+            .collect();
+
+        Mesh::from_parts(vertices, self.indices().to_vec())
+    }
+}
+
+/// A seeded classic (Ken Perlin-style) 3D gradient noise, used as the base octave sampled by `displace_fbm`/`displace_turbulence`.
+struct GradientNoise {
+    permutation: [u8; 512],
+}
+
+impl GradientNoise {
+    fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut table: Vec<u8> = (0..=255).collect();
+        table.shuffle(rng);
+
+        let mut permutation = [0u8; 512];
+        for (i, slot) in permutation.iter_mut().enumerate() {
+            *slot = table[i % 256];
+        }
+
+        GradientNoise { permutation }
+    }
+
+    /// Samples the noise field at `point`, returning a value roughly in `[-1, 1]`.
+    fn sample(&self, point: Vec3) -> f32 {
+        let p = &self.permutation;
+
+        let xi = (point.x.floor() as i32 & 255) as usize;
+        let yi = (point.y.floor() as i32 & 255) as usize;
+        let zi = (point.z.floor() as i32 & 255) as usize;
+
+        let xf = point.x - point.x.floor();
+        let yf = point.y - point.y.floor();
+        let zf = point.z - point.z.floor();
+
+        let u = fade(xf);
+        let v = fade(yf);
+        let w = fade(zf);
+
+        let a = p[xi] as usize + yi;
+        let aa = p[a] as usize + zi;
+        let ab = p[a + 1] as usize + zi;
+        let b = p[xi + 1] as usize + yi;
+        let ba = p[b] as usize + zi;
+        let bb = p[b + 1] as usize + zi;
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p[aa], xf, yf, zf), grad(p[ba], xf - 1.0, yf, zf)),
+                lerp(
+                    u,
+                    grad(p[ab], xf, yf - 1.0, zf),
+                    grad(p[bb], xf - 1.0, yf - 1.0, zf),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p[aa + 1], xf, yf, zf - 1.0),
+                    grad(p[ba + 1], xf - 1.0, yf, zf - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p[ab + 1], xf, yf - 1.0, zf - 1.0),
+                    grad(p[bb + 1], xf - 1.0, yf - 1.0, zf - 1.0),
+                ),
+            ),
+        )
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+fn grad(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}